@@ -0,0 +1,124 @@
+use crate::hash::{generic_hash_into_buffer, generic_hash_var_length};
+use crate::traits::HashParams;
+use franklin_crypto::bellman::Engine;
+
+/// Hashes many inputs against one shared, already-constructed [`HashParams`]
+/// instance, amortizing the round-constant/MDS setup that
+/// `RescueParams`/`PoseidonParams::default()` would otherwise redo on every
+/// call, and the padded-input buffer allocation `hash_many` would otherwise
+/// redo on every call, across thousands of leaves, e.g. when building the
+/// levels of a Merkle tree.
+pub struct BatchHasher<
+    E: Engine,
+    P: HashParams<E, STATE_WIDTH, RATE>,
+    const STATE_WIDTH: usize,
+    const RATE: usize,
+> {
+    params: P,
+    scratch: Vec<E::Fr>,
+}
+
+impl<E: Engine, P: HashParams<E, STATE_WIDTH, RATE>, const STATE_WIDTH: usize, const RATE: usize>
+    BatchHasher<E, P, STATE_WIDTH, RATE>
+{
+    /// Takes ownership of a single `HashParams` instance (with its
+    /// `HashType` already chosen via `with_hash_type`) to drive every hash
+    /// below.
+    pub fn new(params: P) -> Self {
+        Self {
+            params,
+            scratch: Vec::new(),
+        }
+    }
+
+    /// Hashes each fixed-length input in `inputs` independently, reusing
+    /// this hasher's parameter set and scratch padding buffer instead of
+    /// rebuilding/reallocating them per call.
+    pub fn hash_many<const LENGTH: usize>(
+        &mut self,
+        inputs: &[[E::Fr; LENGTH]],
+    ) -> Vec<[E::Fr; RATE]> {
+        inputs
+            .iter()
+            .map(|input| generic_hash_into_buffer(&self.params, input, &mut self.scratch))
+            .collect()
+    }
+
+    /// Streaming variant for building Merkle-tree levels: `level` must hold
+    /// a multiple of `RATE` children, grouped by sibling, and one parent is
+    /// returned per `RATE`-sized chunk. Intended to be called once per
+    /// level while climbing a tree toward its root. Unlike `hash_many`,
+    /// there is no padded-input buffer to reuse here - each chunk is already
+    /// `RATE` long and is hashed from the input slice directly.
+    pub fn hash_level(&self, level: &[E::Fr]) -> Vec<[E::Fr; RATE]> {
+        assert_eq!(
+            level.len() % RATE,
+            0,
+            "level must hold a multiple of RATE children"
+        );
+        level
+            .chunks(RATE)
+            .map(|chunk| generic_hash_var_length(&self.params, chunk))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::generic_hash;
+    use crate::poseidon::PoseidonParams;
+    use crate::traits::HashType;
+    use franklin_crypto::bellman::pairing::bn256::{Bn256, Fr};
+
+    #[test]
+    fn hash_many_matches_individual_generic_hash_calls() {
+        const STATE_WIDTH: usize = 3;
+        const RATE: usize = 2;
+        const LENGTH: usize = 2;
+
+        let params = PoseidonParams::<Bn256, STATE_WIDTH, RATE>::default()
+            .with_hash_type(HashType::ConstantLength(LENGTH));
+        let inputs: Vec<[Fr; LENGTH]> = (0..5)
+            .map(|i| {
+                [
+                    Fr::from_str(&i.to_string()).unwrap(),
+                    Fr::from_str(&(i + 1).to_string()).unwrap(),
+                ]
+            })
+            .collect();
+
+        let mut batch_hasher = BatchHasher::new(params.clone());
+        let batched: Vec<[Fr; RATE]> = batch_hasher.hash_many(&inputs);
+
+        let individual: Vec<[Fr; RATE]> = inputs.iter().map(|input| generic_hash(&params, input)).collect();
+
+        assert_eq!(
+            batched, individual,
+            "hash_many over N inputs must equal N individual generic_hash calls"
+        );
+    }
+
+    #[test]
+    fn hash_level_matches_individual_generic_hash_var_length_calls() {
+        const STATE_WIDTH: usize = 3;
+        const RATE: usize = 2;
+
+        let params = PoseidonParams::<Bn256, STATE_WIDTH, RATE>::default()
+            .with_hash_type(HashType::MerkleTree(RATE));
+        let level: Vec<Fr> = (0..8).map(|i| Fr::from_str(&i.to_string()).unwrap()).collect();
+
+        let batch_hasher = BatchHasher::new(params.clone());
+        let parents = batch_hasher.hash_level(&level);
+
+        let expected: Vec<[Fr; RATE]> = level
+            .chunks(RATE)
+            .map(|chunk| generic_hash_var_length(&params, chunk))
+            .collect();
+
+        assert_eq!(
+            parents, expected,
+            "hash_level must fold each RATE-sized chunk into the same parent generic_hash_var_length would"
+        );
+    }
+}
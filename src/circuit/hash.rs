@@ -0,0 +1,102 @@
+use super::poseidon::gadget_poseidon_round_function;
+use crate::poseidon::PoseidonParams;
+use crate::traits::HashParams;
+use franklin_crypto::bellman::plonk::better_better_cs::cs::ConstraintSystem;
+use franklin_crypto::bellman::{Engine, Field, SynthesisError};
+use franklin_crypto::plonk::circuit::{allocated_num::Num, linear_combination::LinearCombination};
+use std::convert::TryInto;
+
+fn zero_state<E: Engine, const STATE_WIDTH: usize>() -> [LinearCombination<E>; STATE_WIDTH] {
+    (0..STATE_WIDTH)
+        .map(|_| LinearCombination::zero())
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap_or_else(|_: Vec<LinearCombination<E>>| unreachable!())
+}
+
+/// Receives inputs whose length `known` prior(fixed-length), the in-circuit
+/// counterpart of [`crate::hash::generic_hash`]. Loads `params`'s domain tag
+/// into the capacity element before absorption and pads the input with zero
+/// constants to a multiple of `RATE`.
+///
+/// Takes `PoseidonParams` specifically, not the generic `HashParams` trait:
+/// the round schedule driven below (half full rounds, then partial, then
+/// half full, via [`gadget_poseidon_round_function`]) is Poseidon's, and has
+/// no Rescue equivalent in this module, so a `P: HashParams` bound would
+/// promise a generality this function cannot deliver.
+pub(crate) fn circuit_generic_hash<
+    E: Engine,
+    CS: ConstraintSystem<E>,
+    const STATE_WIDTH: usize,
+    const RATE: usize,
+    const LENGTH: usize,
+>(
+    cs: &mut CS,
+    params: &PoseidonParams<E, STATE_WIDTH, RATE>,
+    input: &[Num<E>; LENGTH],
+) -> Result<Vec<Num<E>>, SynthesisError> {
+    let mut state = zero_state::<E, STATE_WIDTH>();
+    state[0].add_assign_constant(params.hash_type().domain_tag());
+
+    let mut padded = input.to_vec();
+    while padded.len() % RATE != 0 {
+        padded.push(Num::Constant(E::Fr::zero()));
+    }
+
+    absorb(cs, params, &mut state, &padded)?;
+    squeeze(cs, &state)
+}
+
+/// Receives inputs whose length `unknown` prior (variable-length), the
+/// in-circuit counterpart of [`crate::hash::generic_hash_var_length`]. Loads
+/// `params`'s domain tag into the capacity element before absorption and
+/// applies no padding rule.
+///
+/// Poseidon-only for the same reason as [`circuit_generic_hash`].
+pub(crate) fn circuit_generic_hash_var_length<
+    E: Engine,
+    CS: ConstraintSystem<E>,
+    const STATE_WIDTH: usize,
+    const RATE: usize,
+>(
+    cs: &mut CS,
+    params: &PoseidonParams<E, STATE_WIDTH, RATE>,
+    input: &[Num<E>],
+) -> Result<Vec<Num<E>>, SynthesisError> {
+    let mut state = zero_state::<E, STATE_WIDTH>();
+    state[0].add_assign_constant(params.hash_type().domain_tag());
+    absorb(cs, params, &mut state, input)?;
+    squeeze(cs, &state)
+}
+
+fn absorb<E: Engine, CS: ConstraintSystem<E>, const STATE_WIDTH: usize, const RATE: usize>(
+    cs: &mut CS,
+    params: &PoseidonParams<E, STATE_WIDTH, RATE>,
+    state: &mut [LinearCombination<E>; STATE_WIDTH],
+    input: &[Num<E>],
+) -> Result<(), SynthesisError> {
+    debug_assert_eq!(
+        input.len() % RATE,
+        0,
+        "input must be padded to a multiple of RATE"
+    );
+    let num_chunks = input.len() / RATE;
+    for (i, chunk) in input.chunks(RATE).enumerate() {
+        for (s, value) in state[STATE_WIDTH - RATE..].iter_mut().zip(chunk.iter()) {
+            s.add_assign_number_with_coeff(value, E::Fr::one());
+        }
+        let is_last_chunk = i + 1 == num_chunks;
+        gadget_poseidon_round_function(cs, params, state, params.skip_last_mds() && is_last_chunk)?;
+    }
+    Ok(())
+}
+
+fn squeeze<E: Engine, CS: ConstraintSystem<E>, const STATE_WIDTH: usize, const RATE: usize>(
+    cs: &mut CS,
+    state: &[LinearCombination<E>; STATE_WIDTH],
+) -> Result<Vec<Num<E>>, SynthesisError> {
+    state[STATE_WIDTH - RATE..]
+        .iter()
+        .map(|lc| lc.clone().into_num(cs))
+        .collect()
+}
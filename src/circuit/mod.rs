@@ -0,0 +1,6 @@
+mod hash;
+pub mod poseidon;
+mod sbox;
+mod utils;
+
+pub(crate) use hash::{circuit_generic_hash, circuit_generic_hash_var_length};
@@ -1,10 +1,10 @@
 use super::hash::{circuit_generic_hash, circuit_generic_hash_var_length};
 use super::sbox::sbox_quintic;
-use super::utils::{matrix_vector_product, mul_by_sparse_matrix};
-use crate::traits::{HashFamily, HashParams};
+use super::utils::{matrix_vector_product, mul_by_sparse_matrix_rows};
 use crate::poseidon::PoseidonParams;
+use crate::traits::{HashFamily, HashParams, HashType, Strength};
 use franklin_crypto::bellman::plonk::better_better_cs::cs::ConstraintSystem;
-use franklin_crypto::bellman::{Field, SynthesisError};
+use franklin_crypto::bellman::SynthesisError;
 use franklin_crypto::{
     bellman::Engine,
     plonk::circuit::{allocated_num::Num, linear_combination::LinearCombination},
@@ -19,10 +19,22 @@ use std::convert::TryInto;
 pub fn gadget_poseidon_hash<E: Engine, CS: ConstraintSystem<E>, const L: usize>(
     cs: &mut CS,
     input: &[Num<E>; L],
+) -> Result<[Num<E>; 2], SynthesisError> {
+    gadget_poseidon_hash_with_strength(cs, input, Strength::Standard)
+}
+
+/// Like [`gadget_poseidon_hash`], but lets the caller opt into
+/// `Strength::Strengthened`'s extra partial-round safety margin, the
+/// in-circuit counterpart of `PoseidonParams::default_with_strength`.
+pub fn gadget_poseidon_hash_with_strength<E: Engine, CS: ConstraintSystem<E>, const L: usize>(
+    cs: &mut CS,
+    input: &[Num<E>; L],
+    strength: Strength,
 ) -> Result<[Num<E>; 2], SynthesisError> {
     const STATE_WIDTH: usize = 3;
     const RATE: usize = 2;
-    let params = PoseidonParams::<E, STATE_WIDTH, RATE>::default();
+    let params = PoseidonParams::<E, STATE_WIDTH, RATE>::default_with_strength(strength)
+        .with_hash_type(HashType::ConstantLength(L));
     circuit_generic_hash(cs, &params, input).map(|res| res.try_into().expect(""))
 }
 
@@ -30,18 +42,44 @@ pub fn gadget_poseidon_hash<E: Engine, CS: ConstraintSystem<E>, const L: usize>(
 /// Also uses custom domain strategy which does not touch to value of capacity element
 /// and does not apply any padding rule.
 /// Uses pre-defined state-width=3 and rate=2.
-pub fn gadget_rescue_hash_var_length<E: Engine, CS: ConstraintSystem<E>>(
+pub fn gadget_poseidon_hash_var_length<E: Engine, CS: ConstraintSystem<E>>(
+    cs: &mut CS,
+    input: &[Num<E>],
+) -> Result<[Num<E>; 2], SynthesisError> {
+    gadget_poseidon_hash_var_length_with_strength(cs, input, Strength::Standard)
+}
+
+/// Like [`gadget_poseidon_hash_var_length`], but lets the caller opt into
+/// `Strength::Strengthened`'s extra partial-round safety margin.
+pub fn gadget_poseidon_hash_var_length_with_strength<E: Engine, CS: ConstraintSystem<E>>(
     cs: &mut CS,
     input: &[Num<E>],
+    strength: Strength,
 ) -> Result<[Num<E>; 2], SynthesisError> {
     // TODO: try to implement const_generics_defaults: https://github.com/rust-lang/rust/issues/44580
     const STATE_WIDTH: usize = 3;
     const RATE: usize = 2;
-    let params = PoseidonParams::<E, STATE_WIDTH, RATE>::default();
+    let params = PoseidonParams::<E, STATE_WIDTH, RATE>::default_with_strength(strength)
+        .with_hash_type(HashType::VariableLength);
     circuit_generic_hash_var_length(cs, &params, input).map(|res| res.try_into().expect(""))
 }
 
-pub fn gadget_generic_rescue_hash<
+pub fn gadget_generic_poseidon_hash<
+    E: Engine,
+    CS: ConstraintSystem<E>,
+    const STATE_WIDTH: usize,
+    const RATE: usize,
+    const LENGTH: usize,
+>(
+    cs: &mut CS,
+    input: &[Num<E>; LENGTH],
+) -> Result<[Num<E>; RATE], SynthesisError> {
+    gadget_generic_poseidon_hash_with_strength(cs, input, Strength::Standard)
+}
+
+/// Like [`gadget_generic_poseidon_hash`], but lets the caller opt into
+/// `Strength::Strengthened`'s extra partial-round safety margin.
+pub fn gadget_generic_poseidon_hash_with_strength<
     E: Engine,
     CS: ConstraintSystem<E>,
     const STATE_WIDTH: usize,
@@ -50,12 +88,28 @@ pub fn gadget_generic_rescue_hash<
 >(
     cs: &mut CS,
     input: &[Num<E>; LENGTH],
+    strength: Strength,
 ) -> Result<[Num<E>; RATE], SynthesisError> {
-    let params = PoseidonParams::<E, STATE_WIDTH, RATE>::default();
+    let params = PoseidonParams::<E, STATE_WIDTH, RATE>::default_with_strength(strength)
+        .with_hash_type(HashType::ConstantLength(LENGTH));
     circuit_generic_hash(cs, &params, input).map(|res| res.try_into().expect(""))
 }
 
-pub fn gadget_generic_rescue_hash_var_length<
+pub fn gadget_generic_poseidon_var_length<
+    E: Engine,
+    CS: ConstraintSystem<E>,
+    const STATE_WIDTH: usize,
+    const RATE: usize,
+>(
+    cs: &mut CS,
+    input: &[Num<E>],
+) -> Result<[Num<E>; RATE], SynthesisError> {
+    gadget_generic_poseidon_var_length_with_strength(cs, input, Strength::Standard)
+}
+
+/// Like [`gadget_generic_poseidon_var_length`], but lets the caller opt
+/// into `Strength::Strengthened`'s extra partial-round safety margin.
+pub fn gadget_generic_poseidon_var_length_with_strength<
     E: Engine,
     CS: ConstraintSystem<E>,
     const STATE_WIDTH: usize,
@@ -63,8 +117,10 @@ pub fn gadget_generic_rescue_hash_var_length<
 >(
     cs: &mut CS,
     input: &[Num<E>],
+    strength: Strength,
 ) -> Result<[Num<E>; RATE], SynthesisError> {
-    let params = PoseidonParams::<E, STATE_WIDTH, RATE>::default();
+    let params = PoseidonParams::<E, STATE_WIDTH, RATE>::default_with_strength(strength)
+        .with_hash_type(HashType::VariableLength);
     circuit_generic_hash_var_length(cs, &params, input).map(|res| res.try_into().expect(""))
 }
 pub(crate) fn gadget_poseidon_round_function<
@@ -77,6 +133,7 @@ pub(crate) fn gadget_poseidon_round_function<
     cs: &mut CS,
     params: &P,
     state: &mut [LinearCombination<E>; STATE_WIDTH],
+    skip_last_mds: bool,
 ) -> Result<(), SynthesisError> {
     assert_eq!(
         params.hash_family(),
@@ -85,80 +142,156 @@ pub(crate) fn gadget_poseidon_round_function<
     );
     assert!(params.number_of_full_rounds() % 2 == 0);
 
+    // Mirrors `poseidon_round_function` round-for-round (plain round
+    // constants, full MDS every round) rather than the Appendix-B
+    // sparse-matrix trick, which this crate does not implement. Running the
+    // same schedule here costs more gates per partial round but keeps
+    // in-circuit and out-of-circuit hashing in agreement.
     let half_of_full_rounds = params.number_of_full_rounds() / 2;
+    let last_partial_round = half_of_full_rounds + params.number_of_partial_rounds();
+    let last_round = last_partial_round + half_of_full_rounds;
 
-    let (m_prime, sparse_matrixes) = &params.optimized_mds_matrixes();
-    let optimized_round_constants = &params.optimized_round_constants();
-
-    // first full rounds
-    for round in 0..half_of_full_rounds {
-        let round_constants = &optimized_round_constants[round];
-
-        // add round constatnts
+    for round in 0..last_round {
+        let round_constants = params.constants_of_round(round);
         for (s, c) in state.iter_mut().zip(round_constants.iter()) {
             s.add_assign_constant(*c);
         }
-        // non linear sbox
-        sbox_quintic::<E, _>(cs, state)?;
 
-        // mul state by mds
-        *state = matrix_vector_product(cs, &params.mds_matrix(), state)?;
+        if round < half_of_full_rounds || round >= last_partial_round {
+            sbox_quintic::<E, _>(cs, state)?;
+        } else {
+            sbox_quintic::<E, _>(cs, &mut state[..1])?;
+        }
+
+        // mul state by mds, skipping the discarded capacity row(s) on the
+        // last round when the caller opted into it
+        *state = if skip_last_mds && round == last_round - 1 {
+            mul_by_sparse_matrix_rows::<E, STATE_WIDTH, RATE>(state, &params.mds_matrix())
+        } else {
+            matrix_vector_product(cs, &params.mds_matrix(), state)?
+        };
     }
 
-    state
-        .iter_mut()
-        .zip(optimized_round_constants[half_of_full_rounds].iter())
-        .for_each(|(a, b)| a.add_assign_constant(*b));
-
-    *state = matrix_vector_product(cs, &m_prime, state)?;
-
-    let mut constants_for_partial_rounds = optimized_round_constants
-        [half_of_full_rounds + 1..half_of_full_rounds + params.number_of_partial_rounds()]
-        .to_vec();
-    constants_for_partial_rounds.push([E::Fr::zero(); STATE_WIDTH]);
-    // in order to reduce gate number we merge two consecutive iteration
-    // which costs 2 gates per each
-    for (round_constant, sparse_matrix) in constants_for_partial_rounds
-        [..constants_for_partial_rounds.len() - 1]
-        .chunks(2)
-        .zip(sparse_matrixes[..sparse_matrixes.len() - 1].chunks(2))
-    {
-        // first
-        sbox_quintic::<E, _>(cs, &mut state[..1])?;
-        state[0].add_assign_constant(round_constant[0][0]);
-        *state = mul_by_sparse_matrix(state, &sparse_matrix[0]);
-
-        // second
-        sbox_quintic::<E, _>(cs, &mut state[..1])?;
-        state[0].add_assign_constant(round_constant[1][0]);
-        *state = mul_by_sparse_matrix(state, &sparse_matrix[1]);
-        // reduce gate cost: LC -> Num -> LC
-        for state in state.iter_mut() {
-            let num = state.clone().into_num(cs).expect("a num");
-            *state = LinearCombination::from(num.get_variable());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::circuit_generic_hash;
+    use crate::poseidon::PoseidonParams;
+    use franklin_crypto::bellman::pairing::bn256::{Bn256, Fr};
+    use franklin_crypto::bellman::plonk::better_better_cs::cs::{
+        PlonkCsWidth4WithNextStepParams, TrivialAssembly, Width4MainGateWithDNext,
+    };
+
+    #[test]
+    fn skip_last_mds_does_not_change_squeezed_output() -> Result<(), SynthesisError> {
+        const STATE_WIDTH: usize = 3;
+        const RATE: usize = 2;
+        let mut cs = TrivialAssembly::<Bn256, PlonkCsWidth4WithNextStepParams, Width4MainGateWithDNext>::new();
+
+        let input = [
+            Num::Constant(Fr::from_str("1").unwrap()),
+            Num::Constant(Fr::from_str("2").unwrap()),
+        ];
+
+        let with_skip =
+            PoseidonParams::<Bn256, STATE_WIDTH, RATE>::default().with_skip_last_mds(true);
+        let without_skip =
+            PoseidonParams::<Bn256, STATE_WIDTH, RATE>::default().with_skip_last_mds(false);
+
+        let with_skip_output = circuit_generic_hash(&mut cs, &with_skip, &input)?;
+        let without_skip_output = circuit_generic_hash(&mut cs, &without_skip, &input)?;
+
+        for (a, b) in with_skip_output.iter().zip(without_skip_output.iter()) {
+            assert_eq!(
+                a.get_value(),
+                b.get_value(),
+                "skipping the discarded capacity row's MDS mix on the last round must not change the squeezed RATE output"
+            );
         }
+        Ok(())
     }
 
-    sbox_quintic::<E, _>(cs, &mut state[..1])?;
-    state[0].add_assign_constant(constants_for_partial_rounds.last().unwrap()[0]);
-    *state = mul_by_sparse_matrix(state, &sparse_matrixes.last().unwrap());
+    #[test]
+    fn circuit_hash_matches_native_hash_for_every_hash_type() -> Result<(), SynthesisError> {
+        use crate::hash::generic_hash;
 
-    // second full round
-    for round in (params.number_of_partial_rounds() + half_of_full_rounds)
-        ..(params.number_of_partial_rounds() + params.number_of_full_rounds())
-    {
-        let round_constants = &optimized_round_constants[round];
+        const STATE_WIDTH: usize = 3;
+        const RATE: usize = 2;
+        let native_input = [Fr::from_str("1").unwrap(), Fr::from_str("2").unwrap()];
+        let circuit_input = [
+            Num::Constant(native_input[0]),
+            Num::Constant(native_input[1]),
+        ];
 
-        // add round constatnts
-        for (s, c) in state.iter_mut().zip(round_constants.iter()) {
-            s.add_assign_constant(*c);
-        }
+        for hash_type in [
+            HashType::ConstantLength(2),
+            HashType::VariableLength,
+            HashType::MerkleTree(RATE),
+            HashType::Encryption,
+        ] {
+            let params =
+                PoseidonParams::<Bn256, STATE_WIDTH, RATE>::default().with_hash_type(hash_type);
 
-        sbox_quintic::<E, _>(cs, state)?;
+            let native_output: [Fr; RATE] = generic_hash(&params, &native_input);
 
-        // mul state by mds
-        *state = matrix_vector_product(cs, &params.mds_matrix(), state)?;
+            let mut cs = TrivialAssembly::<
+                Bn256,
+                PlonkCsWidth4WithNextStepParams,
+                Width4MainGateWithDNext,
+            >::new();
+            let circuit_output = circuit_generic_hash(&mut cs, &params, &circuit_input)?;
+
+            for (native, circuit) in native_output.iter().zip(circuit_output.iter()) {
+                assert_eq!(
+                    circuit.get_value().unwrap(),
+                    *native,
+                    "in-circuit and native hashing must agree on the domain-separated output"
+                );
+            }
+        }
+        Ok(())
     }
 
-    Ok(())
+    #[test]
+    fn strengthened_gadget_hash_matches_strengthened_native_hash() -> Result<(), SynthesisError> {
+        use crate::hash::generic_hash;
+        use crate::traits::Strength;
+
+        const STATE_WIDTH: usize = 3;
+        const RATE: usize = 2;
+        const LENGTH: usize = 2;
+
+        let params = PoseidonParams::<Bn256, STATE_WIDTH, RATE>::default_with_strength(
+            Strength::Strengthened,
+        )
+        .with_hash_type(HashType::ConstantLength(LENGTH));
+
+        let native_input = [Fr::from_str("1").unwrap(), Fr::from_str("2").unwrap()];
+        let native_output: [Fr; RATE] = generic_hash(&params, &native_input);
+
+        let circuit_input = [
+            Num::Constant(native_input[0]),
+            Num::Constant(native_input[1]),
+        ];
+        let mut cs =
+            TrivialAssembly::<Bn256, PlonkCsWidth4WithNextStepParams, Width4MainGateWithDNext>::new();
+        let circuit_output = gadget_poseidon_hash_with_strength(
+            &mut cs,
+            &circuit_input,
+            Strength::Strengthened,
+        )?;
+
+        for (native, circuit) in native_output.iter().zip(circuit_output.iter()) {
+            assert_eq!(
+                circuit.get_value().unwrap(),
+                *native,
+                "the strengthened gadget path must consume the extended round-constant \
+                 table exactly like the native strengthened permutation"
+            );
+        }
+        Ok(())
+    }
 }
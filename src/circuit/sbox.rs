@@ -0,0 +1,18 @@
+use franklin_crypto::bellman::plonk::better_better_cs::cs::ConstraintSystem;
+use franklin_crypto::bellman::{Engine, SynthesisError};
+use franklin_crypto::plonk::circuit::linear_combination::LinearCombination;
+
+/// Raises every element of `state` to the fifth power in-circuit (`alpha = 5`).
+pub(crate) fn sbox_quintic<E: Engine, CS: ConstraintSystem<E>>(
+    cs: &mut CS,
+    state: &mut [LinearCombination<E>],
+) -> Result<(), SynthesisError> {
+    for s in state.iter_mut() {
+        let base = s.clone().into_num(cs)?;
+        let squared = base.square(cs)?;
+        let quartic = squared.square(cs)?;
+        let quintic = quartic.mul(cs, &base)?;
+        *s = LinearCombination::from(quintic.get_variable());
+    }
+    Ok(())
+}
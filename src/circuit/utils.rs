@@ -0,0 +1,62 @@
+use franklin_crypto::bellman::plonk::better_better_cs::cs::ConstraintSystem;
+use franklin_crypto::bellman::Engine;
+use franklin_crypto::plonk::circuit::linear_combination::LinearCombination;
+use std::convert::TryInto;
+
+/// Multiplies `state` by `matrix` in-circuit: `state := matrix * state`.
+pub(crate) fn matrix_vector_product<
+    E: Engine,
+    CS: ConstraintSystem<E>,
+    const STATE_WIDTH: usize,
+>(
+    _cs: &mut CS,
+    matrix: &[[E::Fr; STATE_WIDTH]; STATE_WIDTH],
+    state: &[LinearCombination<E>; STATE_WIDTH],
+) -> Result<[LinearCombination<E>; STATE_WIDTH], franklin_crypto::bellman::SynthesisError> {
+    Ok(mul_by_sparse_matrix(state, matrix))
+}
+
+/// Multiplies `state` by `matrix` in-circuit without allocating new
+/// constraints, relying on the fact that a `LinearCombination` can absorb an
+/// arbitrary number of scaled terms for free.
+pub(crate) fn mul_by_sparse_matrix<E: Engine, const STATE_WIDTH: usize>(
+    state: &[LinearCombination<E>; STATE_WIDTH],
+    matrix: &[[E::Fr; STATE_WIDTH]; STATE_WIDTH],
+) -> [LinearCombination<E>; STATE_WIDTH] {
+    matrix
+        .iter()
+        .map(|row| {
+            let mut acc = LinearCombination::zero();
+            for (coeff, s) in row.iter().zip(state.iter()) {
+                acc.add_assign_scaled(s, *coeff);
+            }
+            acc
+        })
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap_or_else(|_: Vec<LinearCombination<E>>| unreachable!())
+}
+
+/// Like [`mul_by_sparse_matrix`], but only computes the last `RATE` rows (the
+/// ones a squeeze reads), leaving the discarded capacity row(s) as zero. Used
+/// to skip mixing the capacity element(s) on a permutation's last round.
+pub(crate) fn mul_by_sparse_matrix_rows<E: Engine, const STATE_WIDTH: usize, const RATE: usize>(
+    state: &[LinearCombination<E>; STATE_WIDTH],
+    matrix: &[[E::Fr; STATE_WIDTH]; STATE_WIDTH],
+) -> [LinearCombination<E>; STATE_WIDTH] {
+    (0..STATE_WIDTH)
+        .map(|row| {
+            if row < STATE_WIDTH - RATE {
+                LinearCombination::zero()
+            } else {
+                let mut acc = LinearCombination::zero();
+                for (coeff, s) in matrix[row].iter().zip(state.iter()) {
+                    acc.add_assign_scaled(s, *coeff);
+                }
+                acc
+            }
+        })
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap_or_else(|_: Vec<LinearCombination<E>>| unreachable!())
+}
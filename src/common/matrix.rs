@@ -0,0 +1,43 @@
+use franklin_crypto::bellman::{Engine, Field};
+
+/// Multiplies `state` by `matrix` in place: `state := matrix * state`.
+pub(crate) fn mmul_assign<E: Engine, const STATE_WIDTH: usize>(
+    matrix: &[[E::Fr; STATE_WIDTH]; STATE_WIDTH],
+    state: &mut [E::Fr; STATE_WIDTH],
+) {
+    let mut result = [E::Fr::zero(); STATE_WIDTH];
+
+    for (row, result_entry) in matrix.iter().zip(result.iter_mut()) {
+        for (coeff, value) in row.iter().zip(state.iter()) {
+            let mut term = *coeff;
+            term.mul_assign(value);
+            result_entry.add_assign(&term);
+        }
+    }
+
+    *state = result;
+}
+
+/// Multiplies `state` by `matrix`, writing back only the last `RATE` rows
+/// (the ones a squeeze reads). Used to skip mixing the capacity element(s)
+/// on a permutation's last round, when the caller knows nothing will read
+/// them again.
+pub(crate) fn mmul_assign_rows<E: Engine, const STATE_WIDTH: usize, const RATE: usize>(
+    matrix: &[[E::Fr; STATE_WIDTH]; STATE_WIDTH],
+    state: &mut [E::Fr; STATE_WIDTH],
+) {
+    let input = *state;
+
+    for (row, result_entry) in matrix[STATE_WIDTH - RATE..]
+        .iter()
+        .zip(state[STATE_WIDTH - RATE..].iter_mut())
+    {
+        let mut acc = E::Fr::zero();
+        for (coeff, value) in row.iter().zip(input.iter()) {
+            let mut term = *coeff;
+            term.mul_assign(value);
+            acc.add_assign(&term);
+        }
+        *result_entry = acc;
+    }
+}
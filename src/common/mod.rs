@@ -0,0 +1,5 @@
+pub(crate) mod matrix;
+pub(crate) mod modinv;
+pub(crate) mod sbox;
+pub(crate) mod seed;
+pub(crate) mod serialization;
@@ -0,0 +1,52 @@
+use crate::common::serialization::fr_byte_len;
+use franklin_crypto::bellman::{PrimeField, PrimeFieldRepr};
+use num_bigint::{BigInt, Sign};
+use num_traits::{One, Zero};
+
+fn repr_to_bigint<R: PrimeFieldRepr>(repr: &R) -> BigInt {
+    let mut bytes = Vec::new();
+    repr.write_be(&mut bytes)
+        .expect("writing to a Vec<u8> never fails");
+    BigInt::from_bytes_be(Sign::Plus, &bytes)
+}
+
+fn bigint_to_repr<F: PrimeField>(value: &BigInt) -> F::Repr {
+    let (_, mut bytes) = value.to_bytes_be();
+    while bytes.len() < fr_byte_len::<F>() {
+        bytes.insert(0, 0);
+    }
+    let mut repr = F::Repr::default();
+    repr.read_be(&bytes[..])
+        .expect("value is less than the field modulus");
+    repr
+}
+
+/// Extended Euclidean algorithm: returns `(gcd, x, y)` with `a*x + b*y = gcd`.
+fn extended_gcd(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
+    if b.is_zero() {
+        (a.clone(), BigInt::one(), BigInt::zero())
+    } else {
+        let (gcd, x, y) = extended_gcd(b, &(a % b));
+        (gcd, y.clone(), x - (a / b) * y)
+    }
+}
+
+/// Computes `d` such that `(x^alpha)^d = x` for every `x` in `F`'s
+/// multiplicative group, i.e. the inverse of `alpha` modulo the group order
+/// `p - 1`, encoded as an `F::Repr` so it can become an `F` via
+/// `F::from_repr` and then feed `sbox`'s `pow`. `alpha` must be coprime to
+/// `p - 1`, which holds for the `alpha = 5` S-box this crate uses whenever
+/// `p mod 5 != 1`.
+pub(crate) fn alpha_inv_repr<F: PrimeField>(alpha: u64) -> F::Repr {
+    let group_order = repr_to_bigint(&F::char()) - BigInt::one();
+    let alpha = BigInt::from(alpha);
+
+    let (gcd, x, _) = extended_gcd(&alpha, &group_order);
+    assert!(
+        gcd.is_one(),
+        "alpha must be coprime to the multiplicative group order p - 1"
+    );
+
+    let inverse = ((x % &group_order) + &group_order) % &group_order;
+    bigint_to_repr::<F>(&inverse)
+}
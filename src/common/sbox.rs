@@ -0,0 +1,9 @@
+use franklin_crypto::bellman::{Engine, Field, PrimeField};
+
+/// Raises every element of `state` to the `power`-th power in place.
+pub(crate) fn sbox<E: Engine>(power: E::Fr, state: &mut [E::Fr]) {
+    let power = power.into_repr();
+    for s in state.iter_mut() {
+        *s = s.pow(&power);
+    }
+}
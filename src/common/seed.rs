@@ -0,0 +1,267 @@
+use blake2::{Blake2s, Digest};
+use franklin_crypto::bellman::{Field, PrimeField, PrimeFieldRepr};
+
+/// Repeatedly hashes `personalization || seed || counter` with Blake2s and
+/// rejection-samples each 32-byte digest into `F`: the digest is read as a
+/// big-endian integer, and any value not below the field modulus is
+/// discarded and a fresh digest drawn; no modular reduction is applied. Mirrors the Grain/Blake2s-style
+/// construction used by RLN to derive parameters from a domain seed.
+pub(crate) fn draw_field_element_from_seed<F: PrimeField>(
+    personalization: &[u8],
+    seed: &[u8],
+    counter: &mut u64,
+) -> F {
+    loop {
+        let mut hasher = Blake2s::new();
+        hasher.update(personalization);
+        hasher.update(seed);
+        hasher.update(&counter.to_le_bytes());
+        *counter += 1;
+
+        let digest = hasher.finalize();
+
+        let mut repr = F::Repr::default();
+        if repr.read_be(&digest[..]).is_err() {
+            continue;
+        }
+
+        if let Ok(fe) = F::from_repr(repr) {
+            return fe;
+        }
+    }
+}
+
+/// Draws `num_rounds` sets of `STATE_WIDTH` round constants from a
+/// personalized seed, one field element at a time.
+pub(crate) fn draw_round_constants_from_seed<F: PrimeField, const STATE_WIDTH: usize>(
+    personalization: &[u8],
+    seed: &[u8],
+    num_rounds: usize,
+) -> Vec<[F; STATE_WIDTH]> {
+    let mut counter = 0u64;
+    (0..num_rounds)
+        .map(|_| {
+            let mut round = [F::zero(); STATE_WIDTH];
+            for c in round.iter_mut() {
+                *c = draw_field_element_from_seed(personalization, seed, &mut counter);
+            }
+            round
+        })
+        .collect()
+}
+
+/// Builds an MDS matrix as a Cauchy matrix `mds[i][j] = (x_i + y_j)^-1` over
+/// `2 * STATE_WIDTH` seeded field elements. A Cauchy matrix is invertible
+/// exactly when every `x_i` is distinct, every `y_j` is distinct, and no
+/// `x_i + y_j` is zero, so a freshly drawn candidate set that violates any
+/// of those is rejected and redrawn from the same (continuing) stream
+/// instead of risking a singular matrix or panicking on a zero denominator.
+pub(crate) fn draw_cauchy_mds_matrix_from_seed<F: PrimeField, const STATE_WIDTH: usize>(
+    personalization: &[u8],
+    seed: &[u8],
+) -> [[F; STATE_WIDTH]; STATE_WIDTH] {
+    // Domain-separated from `draw_round_constants_from_seed`, which draws
+    // from the same `personalization`/`seed` starting at counter 0 - without
+    // this, `xs[0]` would equal `round_constants[0][0]`, correlating the MDS
+    // matrix with the round constants.
+    let mds_personalization = [personalization, b"-MDS"].concat();
+    let mut counter = 0u64;
+
+    'retry: loop {
+        let mut xs = [F::zero(); STATE_WIDTH];
+        let mut ys = [F::zero(); STATE_WIDTH];
+
+        for x in xs.iter_mut() {
+            *x = draw_field_element_from_seed(&mds_personalization, seed, &mut counter);
+        }
+        for y in ys.iter_mut() {
+            *y = draw_field_element_from_seed(&mds_personalization, seed, &mut counter);
+        }
+
+        for i in 0..STATE_WIDTH {
+            for j in (i + 1)..STATE_WIDTH {
+                if xs[i] == xs[j] || ys[i] == ys[j] {
+                    continue 'retry;
+                }
+            }
+        }
+        for x in xs.iter() {
+            for y in ys.iter() {
+                let mut entry = *x;
+                entry.add_assign(y);
+                if entry.is_zero() {
+                    continue 'retry;
+                }
+            }
+        }
+
+        let mut mds = [[F::zero(); STATE_WIDTH]; STATE_WIDTH];
+        for i in 0..STATE_WIDTH {
+            for j in 0..STATE_WIDTH {
+                let mut entry = xs[i];
+                entry.add_assign(&ys[j]);
+                mds[i][j] = entry.inverse().expect("checked non-zero above");
+            }
+        }
+
+        return mds;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use franklin_crypto::bellman::pairing::bn256::Fr;
+
+    const PERSONALIZATION: &[u8] = b"TEST_SEED";
+
+    /// Inverts a square matrix over `F` by Gauss-Jordan elimination, solely
+    /// to check `draw_cauchy_mds_matrix_from_seed`'s output is actually
+    /// invertible rather than merely non-panicking.
+    fn invert_matrix<F: PrimeField, const N: usize>(matrix: &[[F; N]; N]) -> [[F; N]; N] {
+        let mut aug = [[F::zero(); N]; N];
+        let mut inv = [[F::zero(); N]; N];
+        for i in 0..N {
+            aug[i] = matrix[i];
+            inv[i][i] = F::one();
+        }
+
+        for col in 0..N {
+            let pivot_row = (col..N)
+                .find(|&r| !aug[r][col].is_zero())
+                .expect("matrix is singular");
+            aug.swap(col, pivot_row);
+            inv.swap(col, pivot_row);
+
+            let pivot_inv = aug[col][col].inverse().expect("checked non-zero above");
+            for v in aug[col].iter_mut() {
+                v.mul_assign(&pivot_inv);
+            }
+            for v in inv[col].iter_mut() {
+                v.mul_assign(&pivot_inv);
+            }
+
+            for row in 0..N {
+                if row == col {
+                    continue;
+                }
+                let factor = aug[row][col];
+                if factor.is_zero() {
+                    continue;
+                }
+                for c in 0..N {
+                    let mut term = aug[col][c];
+                    term.mul_assign(&factor);
+                    aug[row][c].sub_assign(&term);
+
+                    let mut term = inv[col][c];
+                    term.mul_assign(&factor);
+                    inv[row][c].sub_assign(&term);
+                }
+            }
+        }
+
+        inv
+    }
+
+    fn mat_mul<F: PrimeField, const N: usize>(a: &[[F; N]; N], b: &[[F; N]; N]) -> [[F; N]; N] {
+        let mut result = [[F::zero(); N]; N];
+        for i in 0..N {
+            for j in 0..N {
+                let mut acc = F::zero();
+                for k in 0..N {
+                    let mut term = a[i][k];
+                    term.mul_assign(&b[k][j]);
+                    acc.add_assign(&term);
+                }
+                result[i][j] = acc;
+            }
+        }
+        result
+    }
+
+    fn is_identity<F: PrimeField, const N: usize>(matrix: &[[F; N]; N]) -> bool {
+        for i in 0..N {
+            for j in 0..N {
+                let expected = if i == j { F::one() } else { F::zero() };
+                if matrix[i][j] != expected {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    #[test]
+    fn field_element_from_seed_is_deterministic() {
+        let mut counter_a = 0u64;
+        let mut counter_b = 0u64;
+        let a: Fr = draw_field_element_from_seed(PERSONALIZATION, b"seed-a", &mut counter_a);
+        let b: Fr = draw_field_element_from_seed(PERSONALIZATION, b"seed-a", &mut counter_b);
+        assert_eq!(a, b);
+        assert_eq!(counter_a, counter_b);
+    }
+
+    #[test]
+    fn round_constants_from_seed_are_deterministic() {
+        const STATE_WIDTH: usize = 3;
+        let first = draw_round_constants_from_seed::<Fr, STATE_WIDTH>(
+            PERSONALIZATION,
+            b"seed-a",
+            8,
+        );
+        let second = draw_round_constants_from_seed::<Fr, STATE_WIDTH>(
+            PERSONALIZATION,
+            b"seed-a",
+            8,
+        );
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn round_constants_differ_across_seeds() {
+        const STATE_WIDTH: usize = 3;
+        let from_a = draw_round_constants_from_seed::<Fr, STATE_WIDTH>(
+            PERSONALIZATION,
+            b"seed-a",
+            8,
+        );
+        let from_b = draw_round_constants_from_seed::<Fr, STATE_WIDTH>(
+            PERSONALIZATION,
+            b"seed-b",
+            8,
+        );
+        assert_ne!(from_a, from_b);
+    }
+
+    #[test]
+    fn cauchy_mds_matrix_from_seed_is_deterministic() {
+        const STATE_WIDTH: usize = 3;
+        let first = draw_cauchy_mds_matrix_from_seed::<Fr, STATE_WIDTH>(PERSONALIZATION, b"seed-a");
+        let second =
+            draw_cauchy_mds_matrix_from_seed::<Fr, STATE_WIDTH>(PERSONALIZATION, b"seed-a");
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn cauchy_mds_matrix_differs_across_seeds() {
+        const STATE_WIDTH: usize = 3;
+        let from_a =
+            draw_cauchy_mds_matrix_from_seed::<Fr, STATE_WIDTH>(PERSONALIZATION, b"seed-a");
+        let from_b =
+            draw_cauchy_mds_matrix_from_seed::<Fr, STATE_WIDTH>(PERSONALIZATION, b"seed-b");
+        assert_ne!(from_a, from_b);
+    }
+
+    #[test]
+    fn cauchy_mds_matrix_from_seed_is_invertible() {
+        const STATE_WIDTH: usize = 3;
+        let mds =
+            draw_cauchy_mds_matrix_from_seed::<Fr, STATE_WIDTH>(PERSONALIZATION, b"seed-a");
+        let inverse = invert_matrix(&mds);
+        assert!(
+            is_identity(&mat_mul(&mds, &inverse)),
+            "mds * mds^-1 must be the identity matrix"
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use crate::traits::HashType;
+use franklin_crypto::bellman::{Engine, Field, PrimeField, PrimeFieldRepr};
+use std::convert::TryInto;
+
+/// Number of bytes a field element's canonical big-endian representation
+/// occupies, derived from its limb count so this works for any `PrimeField`.
+pub(crate) fn fr_byte_len<F: PrimeField>() -> usize {
+    F::Repr::default().as_ref().len() * 8
+}
+
+pub(crate) fn write_fr<F: PrimeField>(buf: &mut Vec<u8>, value: &F) {
+    value
+        .into_repr()
+        .write_be(buf)
+        .expect("writing to a Vec<u8> never fails");
+}
+
+pub(crate) fn write_fr_array<F: PrimeField, const N: usize>(buf: &mut Vec<u8>, values: &[F; N]) {
+    for value in values.iter() {
+        write_fr(buf, value);
+    }
+}
+
+pub(crate) fn write_fr_matrix<F: PrimeField, const N: usize>(
+    buf: &mut Vec<u8>,
+    matrix: &[[F; N]; N],
+) {
+    for row in matrix.iter() {
+        write_fr_array(buf, row);
+    }
+}
+
+pub(crate) fn write_fr_array_vec<F: PrimeField, const N: usize>(
+    buf: &mut Vec<u8>,
+    values: &[[F; N]],
+) {
+    buf.extend_from_slice(&(values.len() as u64).to_le_bytes());
+    for value in values {
+        write_fr_array(buf, value);
+    }
+}
+
+pub(crate) fn write_hash_type<E: Engine>(buf: &mut Vec<u8>, hash_type: &HashType<E>) {
+    match hash_type {
+        HashType::ConstantLength(len) => {
+            buf.push(0);
+            buf.extend_from_slice(&(*len as u64).to_le_bytes());
+        }
+        HashType::VariableLength => buf.push(1),
+        HashType::MerkleTree(arity) => {
+            buf.push(2);
+            buf.extend_from_slice(&(*arity as u64).to_le_bytes());
+        }
+        HashType::Encryption => buf.push(3),
+        HashType::Custom(tag) => {
+            buf.push(4);
+            write_fr(buf, tag);
+        }
+    }
+}
+
+/// Cursor over a byte slice produced by the `write_*` helpers above, used to
+/// reconstruct `RescueParams`/`PoseidonParams` in the same field order they
+/// were written in.
+pub(crate) struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let slice = self.bytes.get(self.pos..self.pos + len)?;
+        self.pos += len;
+        Some(slice)
+    }
+
+    pub(crate) fn read_u64(&mut self) -> Option<u64> {
+        let bytes: [u8; 8] = self.take(8)?.try_into().ok()?;
+        Some(u64::from_le_bytes(bytes))
+    }
+
+    pub(crate) fn read_bool(&mut self) -> Option<bool> {
+        Some(self.take(1)?[0] != 0)
+    }
+
+    pub(crate) fn read_fr<F: PrimeField>(&mut self) -> Option<F> {
+        let bytes = self.take(fr_byte_len::<F>())?;
+        let mut repr = F::Repr::default();
+        repr.read_be(bytes).ok()?;
+        F::from_repr(repr).ok()
+    }
+
+    pub(crate) fn read_fr_array<F: PrimeField, const N: usize>(&mut self) -> Option<[F; N]> {
+        let mut out = [F::zero(); N];
+        for value in out.iter_mut() {
+            *value = self.read_fr()?;
+        }
+        Some(out)
+    }
+
+    pub(crate) fn read_fr_matrix<F: PrimeField, const N: usize>(&mut self) -> Option<[[F; N]; N]> {
+        let mut out = [[F::zero(); N]; N];
+        for row in out.iter_mut() {
+            *row = self.read_fr_array()?;
+        }
+        Some(out)
+    }
+
+    pub(crate) fn read_fr_array_vec<F: PrimeField, const N: usize>(
+        &mut self,
+    ) -> Option<Vec<[F; N]>> {
+        let len = self.read_u64()? as usize;
+        (0..len).map(|_| self.read_fr_array()).collect()
+    }
+
+    pub(crate) fn read_hash_type<E: Engine>(&mut self) -> Option<HashType<E>> {
+        match self.take(1)?[0] {
+            0 => Some(HashType::ConstantLength(self.read_u64()? as usize)),
+            1 => Some(HashType::VariableLength),
+            2 => Some(HashType::MerkleTree(self.read_u64()? as usize)),
+            3 => Some(HashType::Encryption),
+            4 => Some(HashType::Custom(self.read_fr()?)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::poseidon::poseidon_round_function;
+use crate::rescue::rescue_round_function;
+use crate::traits::{HashFamily, HashParams};
+use franklin_crypto::bellman::{Engine, Field};
+
+pub(crate) fn round_function<
+    E: Engine,
+    P: HashParams<E, STATE_WIDTH, RATE>,
+    const STATE_WIDTH: usize,
+    const RATE: usize,
+>(
+    params: &P,
+    state: &mut [E::Fr; STATE_WIDTH],
+    skip_last_mds: bool,
+) {
+    match params.hash_family() {
+        HashFamily::Rescue => rescue_round_function(params, state, skip_last_mds),
+        HashFamily::Poseidon => poseidon_round_function(params, state, skip_last_mds),
+    }
+}
+
+fn absorb<
+    E: Engine,
+    P: HashParams<E, STATE_WIDTH, RATE>,
+    const STATE_WIDTH: usize,
+    const RATE: usize,
+>(
+    params: &P,
+    state: &mut [E::Fr; STATE_WIDTH],
+    input: &[E::Fr],
+) {
+    debug_assert_eq!(input.len() % RATE, 0, "input must be padded to a multiple of RATE");
+    let num_chunks = input.len() / RATE;
+    for (i, chunk) in input.chunks(RATE).enumerate() {
+        for (s, value) in state[STATE_WIDTH - RATE..].iter_mut().zip(chunk.iter()) {
+            s.add_assign(value);
+        }
+        let is_last_chunk = i + 1 == num_chunks;
+        round_function(params, state, params.skip_last_mds() && is_last_chunk);
+    }
+}
+
+fn squeeze<E: Engine, const STATE_WIDTH: usize, const RATE: usize>(
+    state: &[E::Fr; STATE_WIDTH],
+) -> [E::Fr; RATE] {
+    let mut output = [E::Fr::zero(); RATE];
+    output.copy_from_slice(&state[STATE_WIDTH - RATE..]);
+    output
+}
+
+/// Receives inputs whose length `known` prior(fixed-length). Loads `params`'s
+/// domain tag into the capacity element before absorption and pads the
+/// input with zeroes to a multiple of `RATE`.
+pub(crate) fn generic_hash<
+    E: Engine,
+    P: HashParams<E, STATE_WIDTH, RATE>,
+    const STATE_WIDTH: usize,
+    const RATE: usize,
+    const LENGTH: usize,
+>(
+    params: &P,
+    input: &[E::Fr; LENGTH],
+) -> [E::Fr; RATE] {
+    let mut state = [E::Fr::zero(); STATE_WIDTH];
+    state[0] = params.hash_type().domain_tag();
+
+    let mut padded = input.to_vec();
+    while padded.len() % RATE != 0 {
+        padded.push(E::Fr::zero());
+    }
+
+    absorb(params, &mut state, &padded);
+    squeeze(&state)
+}
+
+/// Like [`generic_hash`], but pads into the caller-provided `buffer` instead
+/// of allocating a fresh `Vec` on every call, so repeated calls (e.g.
+/// `BatchHasher` hashing thousands of leaves) amortize that allocation.
+pub(crate) fn generic_hash_into_buffer<
+    E: Engine,
+    P: HashParams<E, STATE_WIDTH, RATE>,
+    const STATE_WIDTH: usize,
+    const RATE: usize,
+    const LENGTH: usize,
+>(
+    params: &P,
+    input: &[E::Fr; LENGTH],
+    buffer: &mut Vec<E::Fr>,
+) -> [E::Fr; RATE] {
+    let mut state = [E::Fr::zero(); STATE_WIDTH];
+    state[0] = params.hash_type().domain_tag();
+
+    buffer.clear();
+    buffer.extend_from_slice(input);
+    while buffer.len() % RATE != 0 {
+        buffer.push(E::Fr::zero());
+    }
+
+    absorb(params, &mut state, buffer);
+    squeeze(&state)
+}
+
+/// Receives inputs whose length `unknown` prior (variable-length). Loads
+/// `params`'s domain tag into the capacity element before absorption and
+/// applies no padding rule, so `input` must already be a multiple of `RATE`
+/// long.
+pub(crate) fn generic_hash_var_length<
+    E: Engine,
+    P: HashParams<E, STATE_WIDTH, RATE>,
+    const STATE_WIDTH: usize,
+    const RATE: usize,
+>(
+    params: &P,
+    input: &[E::Fr],
+) -> [E::Fr; RATE] {
+    let mut state = [E::Fr::zero(); STATE_WIDTH];
+    state[0] = params.hash_type().domain_tag();
+    absorb(params, &mut state, input);
+    squeeze(&state)
+}
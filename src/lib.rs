@@ -0,0 +1,12 @@
+mod batch;
+pub mod circuit;
+mod common;
+mod hash;
+pub mod poseidon;
+pub mod rescue;
+mod sponge;
+mod traits;
+
+pub use batch::BatchHasher;
+pub use sponge::{GenericSponge, Sponge};
+pub use traits::{HashFamily, HashParams, HashType, Strength};
@@ -0,0 +1,7 @@
+mod params;
+#[allow(clippy::module_inception)]
+mod poseidon;
+
+pub(crate) use params::poseidon_params;
+pub(crate) use poseidon::poseidon_round_function;
+pub use poseidon::*;
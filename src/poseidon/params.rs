@@ -0,0 +1,103 @@
+use crate::common::modinv::alpha_inv_repr;
+use crate::common::seed::{draw_cauchy_mds_matrix_from_seed, draw_round_constants_from_seed};
+use crate::traits::Strength;
+use franklin_crypto::bellman::{Engine, PrimeField};
+
+const POSEIDON_PERSONALIZATION: &[u8] = b"POSEIDON";
+pub(crate) const DEFAULT_SEED: &[u8] = b"rescue-poseidon default poseidon seed";
+
+/// Generated round constants and MDS matrix for a Poseidon permutation,
+/// produced by either [`poseidon_params`] or [`poseidon_params_from_seed`].
+pub(crate) struct RawPoseidonParams<E: Engine, const STATE_WIDTH: usize> {
+    pub full_rounds: usize,
+    pub partial_rounds: usize,
+    round_constants: Vec<[E::Fr; STATE_WIDTH]>,
+    mds_matrix: [[E::Fr; STATE_WIDTH]; STATE_WIDTH],
+}
+
+impl<E: Engine, const STATE_WIDTH: usize> RawPoseidonParams<E, STATE_WIDTH> {
+    pub fn round_constants(&self) -> Vec<[E::Fr; STATE_WIDTH]> {
+        self.round_constants.clone()
+    }
+
+    pub fn mds_matrix(&self) -> &[[E::Fr; STATE_WIDTH]; STATE_WIDTH] {
+        &self.mds_matrix
+    }
+}
+
+/// Extra fraction of partial rounds `Strength::Strengthened` adds on top of
+/// the base count, as a safety margin against future cryptanalysis.
+const STRENGTHENED_PARTIAL_ROUNDS_MARGIN_PERCENT: usize = 25;
+
+fn number_of_rounds(_state_width: usize, strength: Strength) -> (usize, usize) {
+    // full_rounds, partial_rounds for alpha = 5, 128-bit security margin.
+    let (full_rounds, partial_rounds) = (8, 83);
+    match strength {
+        Strength::Standard => (full_rounds, partial_rounds),
+        Strength::Strengthened => (
+            full_rounds,
+            partial_rounds + partial_rounds * STRENGTHENED_PARTIAL_ROUNDS_MARGIN_PERCENT / 100,
+        ),
+    }
+}
+
+/// `alpha_inv` here is the exponent that inverts the `x -> x^alpha` power
+/// map, i.e. `alpha^-1 mod (p - 1)`, *not* the field inverse `alpha^-1 mod p`
+/// - the latter would not undo `x^5` at all.
+fn alpha_and_inv<E: Engine>() -> (E::Fr, E::Fr) {
+    let alpha = E::Fr::from_str("5").expect("5 is representable in the scalar field");
+    let alpha_inv = E::Fr::from_repr(alpha_inv_repr::<E::Fr>(5))
+        .expect("alpha_inv exponent is less than the field modulus");
+    (alpha, alpha_inv)
+}
+
+/// The crate's default, fixed Poseidon parameter set for a given state
+/// width/rate, generated once from a constant internal seed.
+pub(crate) fn poseidon_params<E: Engine, const STATE_WIDTH: usize, const RATE: usize>(
+) -> (RawPoseidonParams<E, STATE_WIDTH>, E::Fr, E::Fr) {
+    poseidon_params_from_seed::<E, STATE_WIDTH, RATE>(DEFAULT_SEED)
+}
+
+/// Derives a full Poseidon parameter set - round constants and MDS matrix -
+/// deterministically from `seed`, so different protocols can get domain
+/// separated instances without patching the crate.
+pub(crate) fn poseidon_params_from_seed<E: Engine, const STATE_WIDTH: usize, const RATE: usize>(
+    seed: &[u8],
+) -> (RawPoseidonParams<E, STATE_WIDTH>, E::Fr, E::Fr) {
+    poseidon_params_from_seed_with_strength::<E, STATE_WIDTH, RATE>(seed, Strength::Standard)
+}
+
+/// Like [`poseidon_params_from_seed`], but lets the caller opt into
+/// `Strength::Strengthened`'s extra partial-rounds safety margin.
+pub(crate) fn poseidon_params_from_seed_with_strength<
+    E: Engine,
+    const STATE_WIDTH: usize,
+    const RATE: usize,
+>(
+    seed: &[u8],
+    strength: Strength,
+) -> (RawPoseidonParams<E, STATE_WIDTH>, E::Fr, E::Fr) {
+    let (full_rounds, partial_rounds) = number_of_rounds(STATE_WIDTH, strength);
+    let number_of_rounds = full_rounds + partial_rounds;
+
+    let round_constants = draw_round_constants_from_seed::<E::Fr, STATE_WIDTH>(
+        POSEIDON_PERSONALIZATION,
+        seed,
+        number_of_rounds,
+    );
+    let mds_matrix =
+        draw_cauchy_mds_matrix_from_seed::<E::Fr, STATE_WIDTH>(POSEIDON_PERSONALIZATION, seed);
+
+    let (alpha, alpha_inv) = alpha_and_inv::<E>();
+
+    (
+        RawPoseidonParams {
+            full_rounds,
+            partial_rounds,
+            round_constants,
+            mds_matrix,
+        },
+        alpha,
+        alpha_inv,
+    )
+}
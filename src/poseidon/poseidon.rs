@@ -0,0 +1,335 @@
+use crate::common::matrix::{mmul_assign, mmul_assign_rows};
+use crate::common::sbox::sbox;
+use crate::common::serialization::{
+    write_fr, write_fr_array_vec, write_fr_matrix, write_hash_type, ByteReader,
+};
+use crate::hash::{generic_hash, generic_hash_var_length};
+use crate::traits::{HashFamily, HashParams, HashType, Strength};
+use franklin_crypto::bellman::{Engine, Field};
+use std::convert::TryInto;
+
+/// Receives inputs whose length `known` prior(fixed-length).
+/// Loads the `HashType::ConstantLength(L)` domain tag (`L * 2^64`) into the capacity
+/// element and applies a padding rule which makes input size equals to multiple of
+/// rate parameter.
+/// Uses pre-defined state-width=3 and rate=2.
+pub fn poseidon_hash<E: Engine, const L: usize>(input: &[E::Fr; L]) -> [E::Fr; 2] {
+    const STATE_WIDTH: usize = 3;
+    const RATE: usize = 2;
+    let params = PoseidonParams::<E, STATE_WIDTH, RATE>::default()
+        .with_hash_type(HashType::ConstantLength(L));
+    generic_hash(&params, input)
+}
+
+/// Receives inputs whose length `unknown` prior (variable-length).
+/// Loads the `HashType::VariableLength` domain tag (`2^64`) into the capacity element
+/// and does not apply any padding rule.
+/// Uses pre-defined state-width=3 and rate=2.
+pub fn poseidon_hash_var_length<E: Engine>(input: &[E::Fr]) -> [E::Fr; 2] {
+    const STATE_WIDTH: usize = 3;
+    const RATE: usize = 2;
+    let params =
+        PoseidonParams::<E, STATE_WIDTH, RATE>::default().with_hash_type(HashType::VariableLength);
+    generic_hash_var_length(&params, input)
+}
+
+pub fn generic_poseidon_hash<
+    E: Engine,
+    const STATE_WIDTH: usize,
+    const RATE: usize,
+    const LENGTH: usize,
+>(
+    input: &[E::Fr; LENGTH],
+) -> [E::Fr; RATE] {
+    let params = PoseidonParams::<E, STATE_WIDTH, RATE>::default()
+        .with_hash_type(HashType::ConstantLength(LENGTH));
+    generic_hash(&params, input)
+}
+
+pub fn generic_poseidon_var_length<E: Engine, const STATE_WIDTH: usize, const RATE: usize>(
+    input: &[E::Fr],
+) -> [E::Fr; RATE] {
+    let params =
+        PoseidonParams::<E, STATE_WIDTH, RATE>::default().with_hash_type(HashType::VariableLength);
+    generic_hash_var_length(&params, input)
+}
+
+#[derive(Clone, Debug)]
+pub struct PoseidonParams<E: Engine, const STATE_WIDTH: usize, const RATE: usize> {
+    pub full_rounds: usize,
+    pub partial_rounds: usize,
+    pub round_constants: Vec<[E::Fr; STATE_WIDTH]>,
+    pub mds_matrix: [[E::Fr; STATE_WIDTH]; STATE_WIDTH],
+    pub alpha: E::Fr,
+    pub alpha_inv: E::Fr,
+    pub hash_type: HashType<E>,
+    pub skip_last_mds: bool,
+}
+
+impl<E: Engine, const STATE_WIDTH: usize, const RATE: usize> Default
+    for PoseidonParams<E, STATE_WIDTH, RATE>
+{
+    fn default() -> Self {
+        let (params, alpha, alpha_inv) = super::params::poseidon_params::<E, STATE_WIDTH, RATE>();
+        Self::from_raw(params, alpha, alpha_inv)
+    }
+}
+
+impl<E: Engine, const STATE_WIDTH: usize, const RATE: usize> PoseidonParams<E, STATE_WIDTH, RATE> {
+    /// Derives a domain separated parameter set from `seed`, the Poseidon
+    /// equivalent of `RescueParams::from_seed`: round constants are drawn by
+    /// repeatedly hashing a personalized seed and rejection-sampling the
+    /// digest into `E::Fr`, while the MDS matrix is built as a Cauchy matrix
+    /// over seeded field elements, which is invertible by construction.
+    pub fn from_seed(seed: &[u8]) -> Self {
+        Self::from_seed_with_strength(seed, Strength::Standard)
+    }
+
+    /// Like [`Self::from_seed`], but derives the round count from `strength`:
+    /// `Strength::Strengthened` adds a fixed extra margin of partial rounds
+    /// on top of the base count, as a buffer against future cryptanalysis.
+    pub fn from_seed_with_strength(seed: &[u8], strength: Strength) -> Self {
+        let (params, alpha, alpha_inv) =
+            super::params::poseidon_params_from_seed_with_strength::<E, STATE_WIDTH, RATE>(
+                seed, strength,
+            );
+        Self::from_raw(params, alpha, alpha_inv)
+    }
+
+    /// Like [`Default::default`], but derives the round count from
+    /// `strength` instead of always using `Strength::Standard`.
+    pub fn default_with_strength(strength: Strength) -> Self {
+        Self::from_seed_with_strength(super::params::DEFAULT_SEED, strength)
+    }
+
+    fn from_raw(
+        params: super::params::RawPoseidonParams<E, STATE_WIDTH>,
+        alpha: E::Fr,
+        alpha_inv: E::Fr,
+    ) -> Self {
+        let partial_rounds = params.partial_rounds;
+        let mds_matrix = *params.mds_matrix();
+        let round_constants = params.round_constants();
+
+        Self {
+            full_rounds: params.full_rounds,
+            partial_rounds,
+            round_constants,
+            mds_matrix,
+            alpha,
+            alpha_inv,
+            hash_type: HashType::VariableLength,
+            skip_last_mds: false,
+        }
+    }
+
+    /// Sets the domain separation tag loaded into the capacity element before
+    /// absorption, so the same constants can be reused across unrelated
+    /// usages (fixed-length hashing, Merkle trees, encryption, ...) without
+    /// their permutations colliding.
+    pub fn with_hash_type(mut self, hash_type: HashType<E>) -> Self {
+        self.hash_type = hash_type;
+        self
+    }
+
+    /// Opts into skipping the MDS mix of the discarded capacity element(s) on
+    /// a permutation's last round, following the RLN "skip last mds mul"
+    /// optimization. The `RATE` elements a squeeze reads are still mixed as
+    /// usual on that round; only the capacity element(s), which the squeeze
+    /// never reads, are left unmixed, so the squeezed output is unchanged.
+    pub fn with_skip_last_mds(mut self, skip_last_mds: bool) -> Self {
+        self.skip_last_mds = skip_last_mds;
+        self
+    }
+
+    /// Serializes this parameter set to bytes so it can be cached to disk
+    /// and reloaded with [`Self::from_bytes`] instead of re-running
+    /// `default`/`from_seed`'s round-constant generation on every process
+    /// start.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&(self.full_rounds as u64).to_le_bytes());
+        buf.extend_from_slice(&(self.partial_rounds as u64).to_le_bytes());
+        write_fr_array_vec(&mut buf, &self.round_constants);
+        write_fr_matrix(&mut buf, &self.mds_matrix);
+        write_fr(&mut buf, &self.alpha);
+        write_fr(&mut buf, &self.alpha_inv);
+        write_hash_type(&mut buf, &self.hash_type);
+        buf.push(self.skip_last_mds as u8);
+        buf
+    }
+
+    /// Reconstructs a parameter set previously serialized by
+    /// [`Self::to_bytes`], returning `None` if `bytes` is truncated or
+    /// malformed.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut reader = ByteReader::new(bytes);
+        Some(Self {
+            full_rounds: reader.read_u64()? as usize,
+            partial_rounds: reader.read_u64()? as usize,
+            round_constants: reader.read_fr_array_vec()?,
+            mds_matrix: reader.read_fr_matrix()?,
+            alpha: reader.read_fr()?,
+            alpha_inv: reader.read_fr()?,
+            hash_type: reader.read_hash_type()?,
+            skip_last_mds: reader.read_bool()?,
+        })
+    }
+}
+
+impl<E: Engine, const STATE_WIDTH: usize, const RATE: usize> serde::Serialize
+    for PoseidonParams<E, STATE_WIDTH, RATE>
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // `serializer.serialize_bytes` alone only round-trips through formats
+        // whose `Deserializer` calls `visit_bytes`; `serde_bytes` pins both
+        // sides to the same byte-buffer representation so this works for
+        // human-readable formats too (see `Self::deserialize`).
+        serde_bytes::serialize(&self.to_bytes(), serializer)
+    }
+}
+
+impl<'de, E: Engine, const STATE_WIDTH: usize, const RATE: usize> serde::Deserialize<'de>
+    for PoseidonParams<E, STATE_WIDTH, RATE>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes: Vec<u8> = serde_bytes::deserialize(deserializer)?;
+        Self::from_bytes(&bytes)
+            .ok_or_else(|| serde::de::Error::custom("invalid serialized PoseidonParams"))
+    }
+}
+
+impl<E: Engine, const STATE_WIDTH: usize, const RATE: usize> HashParams<E, STATE_WIDTH, RATE>
+    for PoseidonParams<E, STATE_WIDTH, RATE>
+{
+    fn hash_family(&self) -> HashFamily {
+        HashFamily::Poseidon
+    }
+
+    fn hash_type(&self) -> HashType<E> {
+        self.hash_type.clone()
+    }
+
+    fn skip_last_mds(&self) -> bool {
+        self.skip_last_mds
+    }
+
+    fn constants_of_round(&self, round: usize) -> [E::Fr; STATE_WIDTH] {
+        self.round_constants[round]
+    }
+
+    fn mds_matrix(&self) -> [[E::Fr; STATE_WIDTH]; STATE_WIDTH] {
+        self.mds_matrix
+    }
+
+    fn number_of_full_rounds(&self) -> usize {
+        self.full_rounds
+    }
+
+    fn number_of_partial_rounds(&self) -> usize {
+        self.partial_rounds
+    }
+
+    fn alpha(&self) -> E::Fr {
+        self.alpha
+    }
+
+    fn alpha_inv(&self) -> E::Fr {
+        self.alpha_inv
+    }
+}
+
+pub(crate) fn poseidon_round_function<
+    E: Engine,
+    P: HashParams<E, STATE_WIDTH, RATE>,
+    const STATE_WIDTH: usize,
+    const RATE: usize,
+>(
+    params: &P,
+    state: &mut [E::Fr; STATE_WIDTH],
+    skip_last_mds: bool,
+) {
+    assert_eq!(
+        params.hash_family(),
+        HashFamily::Poseidon,
+        "Incorrect hash family!"
+    );
+    assert!(params.number_of_full_rounds() % 2 == 0);
+
+    let half_of_full_rounds = params.number_of_full_rounds() / 2;
+    let last_partial_round = half_of_full_rounds + params.number_of_partial_rounds();
+    let last_round = last_partial_round + half_of_full_rounds;
+
+    for round in 0..last_round {
+        state
+            .iter_mut()
+            .zip(params.constants_of_round(round).iter())
+            .for_each(|(s, c)| s.add_assign(c));
+
+        if round < half_of_full_rounds || round >= last_partial_round {
+            sbox::<E>(params.alpha(), state);
+        } else {
+            let mut partial = [state[0]];
+            sbox::<E>(params.alpha(), &mut partial);
+            state[0] = partial[0];
+        }
+
+        if skip_last_mds && round == last_round - 1 {
+            mmul_assign_rows::<E, STATE_WIDTH, RATE>(&params.mds_matrix(), state);
+        } else {
+            mmul_assign::<E, STATE_WIDTH>(&params.mds_matrix(), state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::generic_hash;
+    use franklin_crypto::bellman::pairing::bn256::{Bn256, Fr};
+
+    #[test]
+    fn skip_last_mds_does_not_change_squeezed_output() {
+        const STATE_WIDTH: usize = 3;
+        const RATE: usize = 2;
+        let input = [Fr::from_str("1").unwrap(), Fr::from_str("2").unwrap()];
+
+        let with_skip =
+            PoseidonParams::<Bn256, STATE_WIDTH, RATE>::default().with_skip_last_mds(true);
+        let without_skip =
+            PoseidonParams::<Bn256, STATE_WIDTH, RATE>::default().with_skip_last_mds(false);
+
+        assert_eq!(
+            generic_hash(&with_skip, &input),
+            generic_hash(&without_skip, &input),
+            "skipping the discarded capacity row's MDS mix on the last round must not change the squeezed RATE output"
+        );
+    }
+
+    #[test]
+    fn to_bytes_from_bytes_round_trip_preserves_hash_output() {
+        const STATE_WIDTH: usize = 3;
+        const RATE: usize = 2;
+        let input = [Fr::from_str("1").unwrap(), Fr::from_str("2").unwrap()];
+
+        let params = PoseidonParams::<Bn256, STATE_WIDTH, RATE>::default();
+        let restored = PoseidonParams::<Bn256, STATE_WIDTH, RATE>::from_bytes(&params.to_bytes())
+            .expect("freshly serialized params must deserialize");
+
+        assert_eq!(generic_hash(&params, &input), generic_hash(&restored, &input));
+    }
+
+    #[test]
+    fn serde_round_trip_preserves_hash_output() {
+        const STATE_WIDTH: usize = 3;
+        const RATE: usize = 2;
+        let input = [Fr::from_str("1").unwrap(), Fr::from_str("2").unwrap()];
+
+        let params = PoseidonParams::<Bn256, STATE_WIDTH, RATE>::default();
+        let encoded = bincode::serialize(&params).expect("serde serialization must succeed");
+        let restored: PoseidonParams<Bn256, STATE_WIDTH, RATE> =
+            bincode::deserialize(&encoded).expect("serde deserialization must succeed");
+
+        assert_eq!(generic_hash(&params, &input), generic_hash(&restored, &input));
+    }
+}
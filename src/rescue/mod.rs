@@ -0,0 +1,7 @@
+mod params;
+#[allow(clippy::module_inception)]
+mod rescue;
+
+pub(crate) use params::rescue_params;
+pub(crate) use rescue::rescue_round_function;
+pub use rescue::*;
@@ -1,6 +1,10 @@
-use crate::common::{matrix::mmul_assign, sbox::sbox};
+use crate::common::{
+    matrix::{mmul_assign, mmul_assign_rows},
+    sbox::sbox,
+    serialization::{write_fr, write_fr_array_vec, write_fr_matrix, write_hash_type, ByteReader},
+};
 use crate::hash::{generic_hash, generic_hash_var_length};
-use crate::traits::{HashFamily, HashParams};
+use crate::traits::{HashFamily, HashParams, HashType, Strength};
 use franklin_crypto::bellman::{Engine, Field};
 use std::convert::TryInto;
 
@@ -12,19 +16,21 @@ use std::convert::TryInto;
 pub fn rescue_hash<E: Engine, const L: usize>(input: &[E::Fr; L]) -> [E::Fr; 2] {
     const STATE_WIDTH: usize = 3;
     const RATE: usize = 2;
-    let params = RescueParams::<E, STATE_WIDTH, RATE>::default();
+    let params =
+        RescueParams::<E, STATE_WIDTH, RATE>::default().with_hash_type(HashType::ConstantLength(L));
     generic_hash(&params, input)
 }
 
 /// Receives inputs whose length `unknown` prior (variable-length).
 /// Also uses custom domain strategy which does not touch to value of capacity element
-/// and does not apply any padding rule. 
+/// and does not apply any padding rule.
 /// Uses pre-defined state-width=3 and rate=2.
 pub fn rescue_hash_var_length<E: Engine>(input: &[E::Fr]) -> [E::Fr; 2] {
     // TODO: try to implement const_generics_defaults: https://github.com/rust-lang/rust/issues/44580
     const STATE_WIDTH: usize = 3;
     const RATE: usize = 2;
-    let params = RescueParams::<E, STATE_WIDTH, RATE>::default();
+    let params =
+        RescueParams::<E, STATE_WIDTH, RATE>::default().with_hash_type(HashType::VariableLength);
     generic_hash_var_length(&params, input)
 }
 
@@ -36,14 +42,16 @@ pub fn generic_rescue_hash<
 >(
     input: &[E::Fr; LENGTH],
 ) -> [E::Fr; RATE] {
-    let params = RescueParams::<E, STATE_WIDTH, RATE>::default();
+    let params = RescueParams::<E, STATE_WIDTH, RATE>::default()
+        .with_hash_type(HashType::ConstantLength(LENGTH));
     generic_hash(&params, input)
 }
 
 pub fn generic_rescue_var_length<E: Engine, const STATE_WIDTH: usize, const RATE: usize>(
     input: &[E::Fr],
 ) -> [E::Fr; RATE] {
-    let params = RescueParams::<E, STATE_WIDTH, RATE>::default();
+    let params =
+        RescueParams::<E, STATE_WIDTH, RATE>::default().with_hash_type(HashType::VariableLength);
     generic_hash_var_length(&params, input)
 }
 #[derive(Clone, Debug)]
@@ -53,6 +61,8 @@ pub struct RescueParams<E: Engine, const STATE_WIDTH: usize, const RATE: usize>
     pub mds_matrix: [[E::Fr; STATE_WIDTH]; STATE_WIDTH],
     pub alpha: E::Fr,
     pub alpha_inv: E::Fr,
+    pub hash_type: HashType<E>,
+    pub skip_last_mds: bool,
 }
 
 impl<E: Engine, const STATE_WIDTH: usize, const RATE: usize> Default
@@ -69,10 +79,126 @@ impl<E: Engine, const STATE_WIDTH: usize, const RATE: usize> Default
             mds_matrix: *params.mds_matrix(),
             alpha,
             alpha_inv,
+            hash_type: HashType::VariableLength,
+            skip_last_mds: false,
         }
     }
 }
 
+impl<E: Engine, const STATE_WIDTH: usize, const RATE: usize> RescueParams<E, STATE_WIDTH, RATE> {
+    /// Derives a domain separated parameter set from `seed`, following the
+    /// Grain/Blake2s-style construction used by RLN: round constants are
+    /// drawn by repeatedly hashing a personalized seed and rejection-sampling
+    /// the digest into `E::Fr`, while the MDS matrix is built as a Cauchy
+    /// matrix over seeded field elements, which is invertible by
+    /// construction. This lets callers instantiate Rescue with a custom tag
+    /// instead of sharing the crate's default constants.
+    pub fn from_seed(seed: &[u8]) -> Self {
+        Self::from_seed_with_strength(seed, Strength::Standard)
+    }
+
+    /// Like [`Self::from_seed`], but derives the round count from `strength`:
+    /// `Strength::Strengthened` adds a fixed extra margin of full rounds on
+    /// top of the base count, as a buffer against future cryptanalysis.
+    pub fn from_seed_with_strength(seed: &[u8], strength: Strength) -> Self {
+        let (params, alpha, alpha_inv) =
+            super::params::rescue_params_from_seed_with_strength::<E, STATE_WIDTH, RATE>(
+                seed, strength,
+            );
+        Self {
+            full_rounds: params.full_rounds,
+            round_constants: params
+                .round_constants()
+                .try_into()
+                .expect("round constants"),
+            mds_matrix: *params.mds_matrix(),
+            alpha,
+            alpha_inv,
+            hash_type: HashType::VariableLength,
+            skip_last_mds: false,
+        }
+    }
+
+    /// Like [`Default::default`], but derives the round count from
+    /// `strength` instead of always using `Strength::Standard`.
+    pub fn default_with_strength(strength: Strength) -> Self {
+        Self::from_seed_with_strength(super::params::DEFAULT_SEED, strength)
+    }
+
+    /// Sets the domain separation tag loaded into the capacity element before
+    /// absorption, so the same constants can be reused across unrelated
+    /// usages (fixed-length hashing, Merkle trees, encryption, ...) without
+    /// their permutations colliding.
+    pub fn with_hash_type(mut self, hash_type: HashType<E>) -> Self {
+        self.hash_type = hash_type;
+        self
+    }
+
+    /// Opts into skipping the MDS mix of the discarded capacity element(s) on
+    /// a permutation's last round, following the RLN "skip last mds mul"
+    /// optimization. The `RATE` elements a squeeze reads are still mixed as
+    /// usual on that round; only the capacity element(s), which the squeeze
+    /// never reads, are left unmixed, so the squeezed output is unchanged.
+    pub fn with_skip_last_mds(mut self, skip_last_mds: bool) -> Self {
+        self.skip_last_mds = skip_last_mds;
+        self
+    }
+
+    /// Serializes this parameter set to bytes so it can be cached to disk
+    /// and reloaded with [`Self::from_bytes`] instead of re-running
+    /// `default`/`from_seed`'s round-constant generation on every process
+    /// start.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&(self.full_rounds as u64).to_le_bytes());
+        write_fr_array_vec(&mut buf, &self.round_constants);
+        write_fr_matrix(&mut buf, &self.mds_matrix);
+        write_fr(&mut buf, &self.alpha);
+        write_fr(&mut buf, &self.alpha_inv);
+        write_hash_type(&mut buf, &self.hash_type);
+        buf.push(self.skip_last_mds as u8);
+        buf
+    }
+
+    /// Reconstructs a parameter set previously serialized by
+    /// [`Self::to_bytes`], returning `None` if `bytes` is truncated or
+    /// malformed.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut reader = ByteReader::new(bytes);
+        Some(Self {
+            full_rounds: reader.read_u64()? as usize,
+            round_constants: reader.read_fr_array_vec()?,
+            mds_matrix: reader.read_fr_matrix()?,
+            alpha: reader.read_fr()?,
+            alpha_inv: reader.read_fr()?,
+            hash_type: reader.read_hash_type()?,
+            skip_last_mds: reader.read_bool()?,
+        })
+    }
+}
+
+impl<E: Engine, const STATE_WIDTH: usize, const RATE: usize> serde::Serialize
+    for RescueParams<E, STATE_WIDTH, RATE>
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // `serializer.serialize_bytes` alone only round-trips through formats
+        // whose `Deserializer` calls `visit_bytes`; `serde_bytes` pins both
+        // sides to the same byte-buffer representation so this works for
+        // human-readable formats too (see `Self::deserialize`).
+        serde_bytes::serialize(&self.to_bytes(), serializer)
+    }
+}
+
+impl<'de, E: Engine, const STATE_WIDTH: usize, const RATE: usize> serde::Deserialize<'de>
+    for RescueParams<E, STATE_WIDTH, RATE>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes: Vec<u8> = serde_bytes::deserialize(deserializer)?;
+        Self::from_bytes(&bytes)
+            .ok_or_else(|| serde::de::Error::custom("invalid serialized RescueParams"))
+    }
+}
+
 impl<E: Engine, const STATE_WIDTH: usize, const RATE: usize> HashParams<E, STATE_WIDTH, RATE>
     for RescueParams<E, STATE_WIDTH, RATE>
 {
@@ -80,6 +206,14 @@ impl<E: Engine, const STATE_WIDTH: usize, const RATE: usize> HashParams<E, STATE
         HashFamily::Rescue
     }
 
+    fn hash_type(&self) -> HashType<E> {
+        self.hash_type.clone()
+    }
+
+    fn skip_last_mds(&self) -> bool {
+        self.skip_last_mds
+    }
+
     fn constants_of_round(&self, round: usize) -> [E::Fr; STATE_WIDTH] {
         self.round_constants[round]
     }
@@ -103,14 +237,6 @@ impl<E: Engine, const STATE_WIDTH: usize, const RATE: usize> HashParams<E, STATE
     fn alpha_inv(&self) -> E::Fr {
         self.alpha_inv
     }
-
-    fn optimized_mds_matrixes(&self) -> (&[[E::Fr; STATE_WIDTH]; STATE_WIDTH], &[[[E::Fr; STATE_WIDTH];STATE_WIDTH]]) {
-        unimplemented!("Rescue doesn't use optimized matrixes")
-    }
-
-    fn optimized_round_constants(&self) -> &[[E::Fr; STATE_WIDTH]] {
-        unimplemented!("Rescue doesn't use optimized round constants")
-    }
 }
 
 pub(crate) fn rescue_round_function<
@@ -121,14 +247,20 @@ pub(crate) fn rescue_round_function<
 >(
     params: &P,
     state: &mut [E::Fr; STATE_WIDTH],
+    skip_last_mds: bool,
 ) {
-    assert_eq!(params.hash_family(), HashFamily::Rescue, "Incorrect hash family!");
+    assert_eq!(
+        params.hash_family(),
+        HashFamily::Rescue,
+        "Incorrect hash family!"
+    );
     // round constants for first step
     state
         .iter_mut()
         .zip(params.constants_of_round(0).iter())
         .for_each(|(s, c)| s.add_assign(c));
 
+    let last_round = 2 * params.number_of_full_rounds() - 1;
     for round in 0..2 * params.number_of_full_rounds() {
         // sbox
         if round & 1 == 0 {
@@ -138,7 +270,11 @@ pub(crate) fn rescue_round_function<
         }
 
         // mds
-        mmul_assign::<E, STATE_WIDTH>(&params.mds_matrix(), state);
+        if skip_last_mds && round == last_round {
+            mmul_assign_rows::<E, STATE_WIDTH, RATE>(&params.mds_matrix(), state);
+        } else {
+            mmul_assign::<E, STATE_WIDTH>(&params.mds_matrix(), state);
+        }
 
         // round constants
         state
@@ -147,3 +283,55 @@ pub(crate) fn rescue_round_function<
             .for_each(|(s, c)| s.add_assign(c));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::generic_hash;
+    use franklin_crypto::bellman::pairing::bn256::{Bn256, Fr};
+
+    #[test]
+    fn skip_last_mds_does_not_change_squeezed_output() {
+        const STATE_WIDTH: usize = 3;
+        const RATE: usize = 2;
+        let input = [Fr::from_str("1").unwrap(), Fr::from_str("2").unwrap()];
+
+        let with_skip =
+            RescueParams::<Bn256, STATE_WIDTH, RATE>::default().with_skip_last_mds(true);
+        let without_skip =
+            RescueParams::<Bn256, STATE_WIDTH, RATE>::default().with_skip_last_mds(false);
+
+        assert_eq!(
+            generic_hash(&with_skip, &input),
+            generic_hash(&without_skip, &input),
+            "skipping the discarded capacity row's MDS mix on the last round must not change the squeezed RATE output"
+        );
+    }
+
+    #[test]
+    fn to_bytes_from_bytes_round_trip_preserves_hash_output() {
+        const STATE_WIDTH: usize = 3;
+        const RATE: usize = 2;
+        let input = [Fr::from_str("1").unwrap(), Fr::from_str("2").unwrap()];
+
+        let params = RescueParams::<Bn256, STATE_WIDTH, RATE>::default();
+        let restored = RescueParams::<Bn256, STATE_WIDTH, RATE>::from_bytes(&params.to_bytes())
+            .expect("freshly serialized params must deserialize");
+
+        assert_eq!(generic_hash(&params, &input), generic_hash(&restored, &input));
+    }
+
+    #[test]
+    fn serde_round_trip_preserves_hash_output() {
+        const STATE_WIDTH: usize = 3;
+        const RATE: usize = 2;
+        let input = [Fr::from_str("1").unwrap(), Fr::from_str("2").unwrap()];
+
+        let params = RescueParams::<Bn256, STATE_WIDTH, RATE>::default();
+        let encoded = bincode::serialize(&params).expect("serde serialization must succeed");
+        let restored: RescueParams<Bn256, STATE_WIDTH, RATE> =
+            bincode::deserialize(&encoded).expect("serde deserialization must succeed");
+
+        assert_eq!(generic_hash(&params, &input), generic_hash(&restored, &input));
+    }
+}
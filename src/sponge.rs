@@ -0,0 +1,100 @@
+use crate::hash::round_function;
+use crate::traits::HashParams;
+use franklin_crypto::bellman::{Engine, Field};
+use std::marker::PhantomData;
+
+/// A streaming, rate-at-a-time hash sponge: absorb arbitrarily many inputs in
+/// chunks of `RATE`, then squeeze out as many field elements as requested.
+pub trait Sponge<E: Engine, const RATE: usize> {
+    fn absorb(&mut self, input: &[E::Fr]);
+
+    fn squeeze(&mut self, num_outputs: Option<usize>) -> Vec<E::Fr>;
+}
+
+/// Sponge construction that is generic over any [`HashParams`] implementation,
+/// so the same absorb/squeeze machinery drives both Rescue and Poseidon.
+pub struct GenericSponge<
+    'a,
+    E: Engine,
+    P: HashParams<E, STATE_WIDTH, RATE>,
+    const STATE_WIDTH: usize,
+    const RATE: usize,
+> {
+    params: &'a P,
+    state: [E::Fr; STATE_WIDTH],
+    buffer: Vec<E::Fr>,
+    _marker: PhantomData<E>,
+}
+
+impl<'a, E: Engine, P: HashParams<E, STATE_WIDTH, RATE>, const STATE_WIDTH: usize, const RATE: usize>
+    From<&'a P> for GenericSponge<'a, E, P, STATE_WIDTH, RATE>
+{
+    fn from(params: &'a P) -> Self {
+        let mut state = [E::Fr::zero(); STATE_WIDTH];
+        state[0] = params.hash_type().domain_tag();
+
+        Self {
+            params,
+            state,
+            buffer: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, E: Engine, P: HashParams<E, STATE_WIDTH, RATE>, const STATE_WIDTH: usize, const RATE: usize>
+    GenericSponge<'a, E, P, STATE_WIDTH, RATE>
+{
+    fn permute(&mut self) {
+        // `skip_last_mds` is always disabled here: a streaming sponge can be
+        // squeezed again after this permutation (e.g. asking for more than
+        // `RATE` outputs), so no single call here is safely "the last round".
+        round_function(self.params, &mut self.state, false);
+    }
+
+    fn absorb_rate(&mut self, chunk: &[E::Fr]) {
+        for (s, value) in self.state[STATE_WIDTH - RATE..].iter_mut().zip(chunk.iter()) {
+            s.add_assign(value);
+        }
+        self.permute();
+    }
+}
+
+impl<'a, E: Engine, P: HashParams<E, STATE_WIDTH, RATE>, const STATE_WIDTH: usize, const RATE: usize>
+    Sponge<E, RATE> for GenericSponge<'a, E, P, STATE_WIDTH, RATE>
+{
+    fn absorb(&mut self, input: &[E::Fr]) {
+        self.buffer.extend_from_slice(input);
+
+        while self.buffer.len() >= RATE {
+            let chunk = self.buffer.drain(..RATE).collect::<Vec<_>>();
+            self.absorb_rate(&chunk);
+        }
+    }
+
+    fn squeeze(&mut self, num_outputs: Option<usize>) -> Vec<E::Fr> {
+        if !self.buffer.is_empty() {
+            let mut chunk = std::mem::take(&mut self.buffer);
+            chunk.resize(RATE, E::Fr::zero());
+            self.absorb_rate(&chunk);
+        }
+
+        let num_outputs = num_outputs.unwrap_or(RATE);
+        let mut output = Vec::with_capacity(num_outputs);
+
+        while output.len() < num_outputs {
+            for value in self.state[STATE_WIDTH - RATE..].iter() {
+                if output.len() == num_outputs {
+                    break;
+                }
+                output.push(*value);
+            }
+
+            if output.len() < num_outputs {
+                self.permute();
+            }
+        }
+
+        output
+    }
+}
@@ -0,0 +1,153 @@
+use franklin_crypto::bellman::{Engine, Field, PrimeField};
+
+/// Identifies which permutation a given `HashParams` implementation drives, so
+/// round functions can assert they were not handed the wrong parameter set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashFamily {
+    Rescue,
+    Poseidon,
+}
+
+/// Security margin selector for the number of rounds a permutation runs,
+/// modeled on neptune's `Strength`. `Strengthened` adds a fixed extra
+/// margin on top of each permutation's base round counts, trading
+/// performance for a buffer against future cryptanalysis. The base counts
+/// are fixed per permutation for the `alpha = 5` S-box; the state width
+/// does not change them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Strength {
+    Standard,
+    Strengthened,
+}
+
+/// Domain separation tag loaded into state element 0 (the capacity slot)
+/// once at initialization, before any input is absorbed. This lets a single
+/// parameter set be reused safely across unrelated usages, since their
+/// permutations start from different initial states and can never collide.
+/// Modeled on the tagging scheme used by neptune.
+#[derive(Clone, Debug)]
+pub enum HashType<E: Engine> {
+    /// Input whose length is fixed and known upfront: tag = `len * 2^64`.
+    ConstantLength(usize),
+    /// Input whose length is not known upfront: tag = `2^64`.
+    VariableLength,
+    /// Merkle tree node hashing with the given `arity` (normally `RATE`):
+    /// tag = `2^arity - 1`.
+    MerkleTree(usize),
+    /// Symmetric encryption/decryption: tag = `2^32`.
+    Encryption,
+    /// Caller-supplied tag, for usages not covered above.
+    Custom(E::Fr),
+}
+
+impl<E: Engine> HashType<E> {
+    /// Computes the field element loaded into the capacity slot for this domain.
+    pub fn domain_tag(&self) -> E::Fr {
+        fn two_pow<F: Field>(power: u64) -> F {
+            let mut tag = F::one();
+            for _ in 0..power {
+                tag.double();
+            }
+            tag
+        }
+
+        match self {
+            HashType::ConstantLength(len) => {
+                let mut tag = two_pow::<E::Fr>(64);
+                tag.mul_assign(
+                    &E::Fr::from_str(&len.to_string()).expect("length fits into the field"),
+                );
+                tag
+            }
+            HashType::VariableLength => two_pow(64),
+            HashType::MerkleTree(arity) => {
+                let mut tag = two_pow::<E::Fr>(*arity as u64);
+                tag.sub_assign(&E::Fr::one());
+                tag
+            }
+            HashType::Encryption => two_pow(32),
+            HashType::Custom(tag) => *tag,
+        }
+    }
+}
+
+/// Common interface implemented by `rescue::RescueParams` and
+/// `poseidon::PoseidonParams`, so the generic sponge and round functions can
+/// be shared between both permutations.
+pub trait HashParams<E: Engine, const STATE_WIDTH: usize, const RATE: usize> {
+    fn hash_family(&self) -> HashFamily;
+
+    fn hash_type(&self) -> HashType<E>;
+
+    /// Whether a permutation's very last round may skip mixing its discarded
+    /// capacity element(s) through the MDS matrix, computing only the `RATE`
+    /// elements a squeeze actually reads. Disabled by default so existing
+    /// outputs never change without the caller opting in.
+    fn skip_last_mds(&self) -> bool;
+
+    fn constants_of_round(&self, round: usize) -> [E::Fr; STATE_WIDTH];
+
+    fn mds_matrix(&self) -> [[E::Fr; STATE_WIDTH]; STATE_WIDTH];
+
+    fn number_of_full_rounds(&self) -> usize;
+
+    fn number_of_partial_rounds(&self) -> usize;
+
+    fn alpha(&self) -> E::Fr;
+
+    fn alpha_inv(&self) -> E::Fr;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use franklin_crypto::bellman::pairing::bn256::{Bn256, Fr};
+
+    #[test]
+    fn domain_tag_matches_neptune_formulas() {
+        assert_eq!(
+            HashType::<Bn256>::ConstantLength(5).domain_tag(),
+            Fr::from_str("92233720368547758080").unwrap(),
+            "ConstantLength(len) must tag as len * 2^64"
+        );
+        assert_eq!(
+            HashType::<Bn256>::VariableLength.domain_tag(),
+            Fr::from_str("18446744073709551616").unwrap(),
+            "VariableLength must tag as 2^64"
+        );
+        assert_eq!(
+            HashType::<Bn256>::MerkleTree(3).domain_tag(),
+            Fr::from_str("7").unwrap(),
+            "MerkleTree(arity) must tag as 2^arity - 1"
+        );
+        assert_eq!(
+            HashType::<Bn256>::Encryption.domain_tag(),
+            Fr::from_str("4294967296").unwrap(),
+            "Encryption must tag as 2^32"
+        );
+        let custom = Fr::from_str("42").unwrap();
+        assert_eq!(
+            HashType::<Bn256>::Custom(custom).domain_tag(),
+            custom,
+            "Custom must tag as the caller-supplied element verbatim"
+        );
+    }
+
+    #[test]
+    fn domain_tag_differs_across_hash_types() {
+        let tags = [
+            HashType::<Bn256>::ConstantLength(2).domain_tag(),
+            HashType::<Bn256>::VariableLength.domain_tag(),
+            HashType::<Bn256>::MerkleTree(2).domain_tag(),
+            HashType::<Bn256>::Encryption.domain_tag(),
+        ];
+        for i in 0..tags.len() {
+            for j in (i + 1)..tags.len() {
+                assert_ne!(
+                    tags[i], tags[j],
+                    "distinct HashType usages must not collide on the same capacity tag"
+                );
+            }
+        }
+    }
+}